@@ -12,6 +12,10 @@ pub struct SealedTestAttributes {
     pub after: Option<Expr>,
     pub cmd_before: Option<TokenStream2>,
     pub cmd_after: Option<TokenStream2>,
+    pub isolate_home: bool,
+    pub dotenv: Vec<String>,
+    pub git_repo: Option<GitRepoSpec>,
+    pub fetch: Option<FetchSpec>,
 }
 
 pub struct EnvVar {
@@ -30,6 +34,23 @@ impl Parse for EnvVar {
     }
 }
 
+pub struct GitRepoSpec {
+    pub commits: Vec<GitCommitSpec>,
+    pub branch: Option<String>,
+}
+
+pub struct GitCommitSpec {
+    pub message: String,
+    pub files: Vec<(String, String)>,
+    pub tag: Option<String>,
+}
+
+pub struct FetchSpec {
+    pub repo: String,
+    pub rev: String,
+    pub into: String,
+}
+
 impl Parse for SealedTestAttributes {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut attributes = SealedTestAttributes {
@@ -39,6 +60,10 @@ impl Parse for SealedTestAttributes {
             after: None,
             cmd_before: None,
             cmd_after: None,
+            isolate_home: false,
+            dotenv: vec![],
+            git_repo: None,
+            fetch: None,
         };
 
         while let Ok(ident) = input.parse::<syn::Ident>() {
@@ -51,6 +76,10 @@ impl Parse for SealedTestAttributes {
                 "after" => attributes.after = Some(input.parse::<Expr>()?),
                 "cmd_before" => attributes.cmd_before = Some(Self::parse_cmd(input)?),
                 "cmd_after" => attributes.cmd_after = Some(Self::parse_cmd(input)?),
+                "isolate_home" => attributes.isolate_home = input.parse::<syn::LitBool>()?.value,
+                "dotenv" => attributes.dotenv = Self::parse_dotenv(input)?,
+                "git_repo" => attributes.git_repo = Some(Self::parse_git_repo(input)?),
+                "fetch" => attributes.fetch = Some(Self::parse_fetch(input)?),
                 other => panic!(
                     "unexpected attribute {}, use 'files', 'env', 'setup' or 'teardown'",
                     other
@@ -109,4 +138,148 @@ impl SealedTestAttributes {
         let cmds = content.parse::<TokenStream2>()?;
         Ok(cmds)
     }
+
+    fn parse_dotenv(input: ParseStream) -> syn::Result<Vec<String>> {
+        if input.peek(syn::token::Bracket) {
+            Self::parse_files(input)
+        } else {
+            let path = input.parse::<LitStr>()?.value();
+            Ok(vec![path])
+        }
+    }
+
+    fn parse_git_repo(input: ParseStream) -> syn::Result<GitRepoSpec> {
+        let content;
+        braced!(content in input);
+
+        let mut commits = vec![];
+        let mut branch = None;
+
+        while let Ok(ident) = content.parse::<syn::Ident>() {
+            content.parse::<Token!(=)>()?;
+
+            match ident.to_string().as_str() {
+                "commits" => commits = Self::parse_commits(&content)?,
+                "branch" => branch = Some(content.parse::<LitStr>()?.value()),
+                other => panic!("unexpected git_repo field {}, use 'commits' or 'branch'", other),
+            }
+
+            if content.peek(Token!(,)) {
+                content.parse::<Token!(,)>()?;
+            }
+        }
+
+        Ok(GitRepoSpec { commits, branch })
+    }
+
+    fn parse_commits(input: ParseStream) -> syn::Result<Vec<GitCommitSpec>> {
+        let content;
+        bracketed!(content in input);
+
+        let mut commits = vec![];
+
+        while !content.is_empty() {
+            if content.peek(LitStr) {
+                let message = content.parse::<LitStr>()?.value();
+                commits.push(GitCommitSpec {
+                    message,
+                    files: vec![],
+                    tag: None,
+                });
+            } else {
+                commits.push(Self::parse_commit(&content)?);
+            }
+
+            if content.peek(Token!(,)) {
+                content.parse::<Token!(,)>()?;
+            }
+        }
+
+        Ok(commits)
+    }
+
+    fn parse_commit(input: ParseStream) -> syn::Result<GitCommitSpec> {
+        let content;
+        braced!(content in input);
+
+        let mut message = None;
+        let mut files = vec![];
+        let mut tag = None;
+
+        while let Ok(ident) = content.parse::<syn::Ident>() {
+            content.parse::<Token!(=)>()?;
+
+            match ident.to_string().as_str() {
+                "message" => message = Some(content.parse::<LitStr>()?.value()),
+                "tag" => tag = Some(content.parse::<LitStr>()?.value()),
+                "files" => files = Self::parse_commit_files(&content)?,
+                other => panic!(
+                    "unexpected commit field {}, use 'message', 'files' or 'tag'",
+                    other
+                ),
+            }
+
+            if content.peek(Token!(,)) {
+                content.parse::<Token!(,)>()?;
+            }
+        }
+
+        Ok(GitCommitSpec {
+            message: message.expect("git_repo commit requires a 'message' field"),
+            files,
+            tag,
+        })
+    }
+
+    fn parse_commit_files(input: ParseStream) -> syn::Result<Vec<(String, String)>> {
+        let content;
+        bracketed!(content in input);
+
+        let mut files = vec![];
+
+        while !content.is_empty() {
+            let file;
+            parenthesized!(file in content);
+            let name = file.parse::<LitStr>()?.value();
+            file.parse::<Token!(,)>()?;
+            let body = file.parse::<LitStr>()?.value();
+            files.push((name, body));
+
+            if content.peek(Token!(,)) {
+                content.parse::<Token!(,)>()?;
+            }
+        }
+
+        Ok(files)
+    }
+
+    fn parse_fetch(input: ParseStream) -> syn::Result<FetchSpec> {
+        let content;
+        braced!(content in input);
+
+        let mut repo = None;
+        let mut rev = None;
+        let mut into = None;
+
+        while let Ok(ident) = content.parse::<syn::Ident>() {
+            content.parse::<Token!(=)>()?;
+
+            match ident.to_string().as_str() {
+                "repo" => repo = Some(content.parse::<LitStr>()?.value()),
+                "rev" => rev = Some(content.parse::<LitStr>()?.value()),
+                "into" => into = Some(content.parse::<LitStr>()?.value()),
+                other => panic!("unexpected fetch field {}, use 'repo', 'rev' or 'into'", other),
+            }
+
+            if content.peek(Token!(,)) {
+                content.parse::<Token!(,)>()?;
+            }
+        }
+
+        Ok(FetchSpec {
+            repo: repo.expect("fetch requires a 'repo' field"),
+            rev: rev.expect("fetch requires a 'rev' field"),
+            into: into.expect("fetch requires an 'into' field"),
+        })
+    }
 }
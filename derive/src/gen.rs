@@ -1,17 +1,35 @@
 use std::path::PathBuf;
+use quote::quote;
+use syn::__private::TokenStream2;
 use syn::{Stmt, parse_quote, Expr};
-use crate::attributes::EnvVar;
+use crate::attributes::{EnvVar, FetchSpec, GitRepoSpec};
 
 pub struct SealedTest {
     stmt: Vec<Stmt>,
 }
 
+/// Builds a statement running `command` (a `std::process::Command` builder expression,
+/// without the trailing `.status()`) and panicking with `action` as context if the process
+/// either fails to spawn or exits with a non-zero status.
+fn checked_command(command: TokenStream2, action: &str) -> Stmt {
+    parse_quote!(
+        {
+            let status = (#command).status().unwrap_or_else(|error| panic!("failed to {}: {}", #action, error));
+
+            if !status.success() {
+                panic!("failed to {}: exited with {}", #action, status);
+            }
+        }
+    )
+}
+
 impl SealedTest {
     pub(crate) fn new() -> Self {
         Self {
             stmt: parse_quote! {
                 let temp_dir = tempfile::TempDir::new().unwrap();
                 std::env::set_current_dir(&temp_dir).unwrap();
+                std::env::set_var("SEALED_TEST_ROOT_DIR", temp_dir.path());
                 let crate_dir: String = std::env::var("CARGO_MANIFEST_DIR").unwrap();
             }
         }
@@ -83,4 +101,225 @@ impl SealedTest {
 
         self
     }
+
+    pub fn with_dotenv(mut self, dotenv: Vec<String>) -> Self {
+        for path in dotenv {
+            self.stmt.push(parse_quote!(
+                {
+                    let dotenv_path = std::path::PathBuf::from(&crate_dir).join(#path);
+                    let dotenv_content = std::fs::read_to_string(&dotenv_path)
+                        .unwrap_or_else(|error| panic!("failed to read dotenv file {:?}. Error = {}", dotenv_path, error));
+
+                    for line in dotenv_content.lines() {
+                        let line = line.trim();
+
+                        if line.is_empty() || line.starts_with('#') {
+                            continue;
+                        }
+
+                        let line = line.strip_prefix("export ").unwrap_or(line);
+
+                        if let Some((key, value)) = line.split_once('=') {
+                            let key = key.trim();
+                            let value = value.trim();
+
+                            let value = if (value.starts_with('"') && value.ends_with('"') && value.len() >= 2)
+                                || (value.starts_with('\'') && value.ends_with('\'') && value.len() >= 2)
+                            {
+                                let quote = value.chars().next().unwrap();
+                                let unquoted = &value[1..value.len() - 1];
+
+                                if quote == '"' {
+                                    let mut decoded = String::with_capacity(unquoted.len());
+                                    let mut chars = unquoted.chars();
+
+                                    while let Some(c) = chars.next() {
+                                        if c != '\\' {
+                                            decoded.push(c);
+                                            continue;
+                                        }
+
+                                        match chars.next() {
+                                            Some('n') => decoded.push('\n'),
+                                            Some('t') => decoded.push('\t'),
+                                            Some('"') => decoded.push('"'),
+                                            Some('\\') => decoded.push('\\'),
+                                            Some(other) => {
+                                                decoded.push('\\');
+                                                decoded.push(other);
+                                            }
+                                            None => decoded.push('\\'),
+                                        }
+                                    }
+
+                                    decoded
+                                } else {
+                                    unquoted.to_string()
+                                }
+                            } else {
+                                value.to_string()
+                            };
+
+                            std::env::set_var(key, value);
+                        }
+                    }
+                }
+            ));
+        }
+
+        self
+    }
+
+    pub fn with_git_repo(mut self, git_repo: Option<GitRepoSpec>) -> Self {
+        let Some(git_repo) = git_repo else {
+            return self;
+        };
+
+        self.stmt.push(checked_command(
+            quote!(std::process::Command::new("git").args(["init", "--quiet"])),
+            "run `git init`",
+        ));
+        self.stmt.push(checked_command(
+            quote!(std::process::Command::new("git").args(["config", "user.name", "sealed-test"])),
+            "configure git user.name",
+        ));
+        self.stmt.push(checked_command(
+            quote!(std::process::Command::new("git").args(["config", "user.email", "sealed-test@example.com"])),
+            "configure git user.email",
+        ));
+
+        for (index, commit) in git_repo.commits.into_iter().enumerate() {
+            let message = commit.message;
+            let date = format!("2000-01-01T00:00:{:02}Z", index % 60);
+
+            for (name, content) in commit.files {
+                self.stmt.push(parse_quote!(
+                    std::fs::write(#name, #content).expect("failed to write git fixture file");
+                ));
+            }
+
+            self.stmt.push(checked_command(
+                quote!(std::process::Command::new("git").args(["add", "."])),
+                "run `git add`",
+            ));
+            self.stmt.push(checked_command(
+                quote!(
+                    std::process::Command::new("git")
+                        .envs([
+                            ("GIT_AUTHOR_NAME", "sealed-test"),
+                            ("GIT_AUTHOR_EMAIL", "sealed-test@example.com"),
+                            ("GIT_AUTHOR_DATE", #date),
+                            ("GIT_COMMITTER_NAME", "sealed-test"),
+                            ("GIT_COMMITTER_EMAIL", "sealed-test@example.com"),
+                            ("GIT_COMMITTER_DATE", #date),
+                        ])
+                        .args(["commit", "--quiet", "--allow-empty", "-m", #message])
+                ),
+                "run `git commit`",
+            ));
+
+            if let Some(tag) = commit.tag {
+                self.stmt.push(checked_command(
+                    quote!(std::process::Command::new("git").args(["tag", #tag])),
+                    "run `git tag`",
+                ));
+            }
+        }
+
+        if let Some(branch) = git_repo.branch {
+            self.stmt.push(checked_command(
+                quote!(std::process::Command::new("git").args(["checkout", "-B", #branch])),
+                "run `git checkout -B`",
+            ));
+        }
+
+        self
+    }
+
+    pub fn with_fetch(mut self, fetch: Option<FetchSpec>) -> Self {
+        let Some(fetch) = fetch else {
+            return self;
+        };
+
+        let repo = fetch.repo;
+        let rev = fetch.rev;
+        let into = fetch.into;
+        let cache_key = Self::fetch_cache_key(&repo, &rev);
+
+        let clone_stmt = checked_command(
+            quote!(std::process::Command::new("git").args(["clone", #repo, clone_dir])),
+            "clone fetch fixture repository",
+        );
+        let checkout_stmt = checked_command(
+            quote!(std::process::Command::new("git").args(["checkout", #rev]).current_dir(&cache_dir)),
+            "checkout fetch fixture revision",
+        );
+
+        self.stmt.push(parse_quote!(
+            {
+                if std::env::var("SEALED_TEST_FETCH").as_deref() != Ok("1") {
+                    panic!(
+                        "this test needs network access to fetch `{}` at `{}` into `{}`, but it was not granted.\n\
+                         Set SEALED_TEST_FETCH=1 to allow it.",
+                        #repo, #rev, #into
+                    );
+                }
+
+                let cache_dir = std::env::temp_dir().join("sealed-test-fetch-cache").join(#cache_key);
+
+                if !cache_dir.exists() {
+                    let clone_dir = cache_dir.to_str().unwrap();
+
+                    #clone_stmt
+                    #checkout_stmt
+                }
+
+                let dest = std::env::current_dir().unwrap().join(#into);
+                let mut opt = fs_extra::dir::CopyOptions::new();
+                opt.copy_inside = true;
+
+                fs_extra::dir::copy(&cache_dir, &dest, &opt).unwrap_or_else(|error| {
+                    panic!("failed to copy {:?} to test directory {:?}. Error = {}", cache_dir, dest, error)
+                });
+            }
+        ));
+
+        self
+    }
+
+    /// Builds a cache directory name unique to a `(repo, rev)` pair, so two `fetch`
+    /// fixtures pointing at different repositories can't collide just because they
+    /// happen to share a non-sha `rev` such as a branch name.
+    fn fetch_cache_key(repo: &str, rev: &str) -> String {
+        let sanitize = |s: &str| -> String {
+            s.chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect()
+        };
+
+        format!("{}-{}", sanitize(repo), sanitize(rev))
+    }
+
+    pub fn with_isolate_home(mut self, isolate_home: bool) -> Self {
+        if isolate_home {
+            self.stmt.push(parse_quote!(
+                {
+                    let home_dir = temp_dir.path().join("home");
+                    std::fs::create_dir_all(&home_dir).unwrap();
+                    std::env::set_var("HOME", &home_dir);
+                    std::env::set_var("XDG_CONFIG_HOME", &home_dir);
+                    std::env::set_var("XDG_DATA_HOME", &home_dir);
+                    std::env::set_var("XDG_CACHE_HOME", &home_dir);
+
+                    #[cfg(windows)]
+                    {
+                        std::env::set_var("USERPROFILE", &home_dir);
+                        std::env::set_var("APPDATA", &home_dir);
+                    }
+                }
+            ));
+        }
+
+        self
+    }
 }
@@ -15,8 +15,12 @@ pub fn sealed_test(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = input_fn.block.stmts.clone();
 
     let test_fn = SealedTest::new()
+        .with_isolate_home(args.isolate_home)
         .with_files(args.files)
+        .with_dotenv(args.dotenv)
         .with_env(args.env)
+        .with_git_repo(args.git_repo)
+        .with_fetch(args.fetch)
         .with_cmd_before(args.cmd_before)
         .with_expr(args.before)
         .with_test(input)
@@ -0,0 +1,134 @@
+//! Snapshot/golden-file matching, modeled on cargo's `lines_match` test helper.
+//!
+//! Expected text may use `[..]` as a wildcard matching any (possibly empty) run of
+//! characters within a line, plus a handful of substitution tokens (`[EXE]`, `[ROOT]`,
+//! `[CWD]`) that are expanded before matching so machine-specific paths normalize away.
+
+use std::path::PathBuf;
+
+/// Returns the root tempdir of the current sealed test, falling back to the current
+/// directory when called outside of a `#[sealed_test]`.
+fn root_dir() -> PathBuf {
+    std::env::var("SEALED_TEST_ROOT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap())
+}
+
+fn expand_tokens(text: &str) -> String {
+    text.replace("[EXE]", std::env::consts::EXE_SUFFIX)
+        .replace("[ROOT]", &root_dir().display().to_string())
+        .replace("[CWD]", &std::env::current_dir().unwrap().display().to_string())
+}
+
+/// Matches a single expected line, which may contain `[..]` wildcards, against a single
+/// actual line. The first fragment must be a prefix of `actual`, the last fragment a
+/// suffix, and each interior fragment must be found in order in between.
+fn lines_match(expected: &str, actual: &str) -> bool {
+    let parts: Vec<&str> = expected.split("[..]").collect();
+
+    if parts.len() == 1 {
+        return expected == actual;
+    }
+
+    if !actual.starts_with(parts[0]) {
+        return false;
+    }
+    let cursor = &actual[parts[0].len()..];
+
+    let last = parts[parts.len() - 1];
+    if !cursor.ends_with(last) {
+        return false;
+    }
+    let mut middle = &cursor[..cursor.len() - last.len()];
+
+    for part in &parts[1..parts.len() - 1] {
+        match middle.find(part) {
+            Some(index) => middle = &middle[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    true
+}
+
+fn diff_message(expected: &[&str], actual: &[&str]) -> String {
+    let mut message = String::from("snapshot does not match:\n");
+    let len = expected.len().max(actual.len());
+
+    for i in 0..len {
+        match (expected.get(i), actual.get(i)) {
+            (Some(expected), Some(actual)) if lines_match(expected, actual) => {
+                message.push_str(&format!("  {actual}\n"));
+            }
+            (Some(expected), Some(actual)) => {
+                message.push_str(&format!("- {expected}\n"));
+                message.push_str(&format!("+ {actual}\n"));
+            }
+            (Some(expected), None) => message.push_str(&format!("- {expected}\n")),
+            (None, Some(actual)) => message.push_str(&format!("+ {actual}\n")),
+            (None, None) => {}
+        }
+    }
+
+    message
+}
+
+/// Compares `actual` against `expected` line by line, returning a diff message on mismatch.
+pub fn assert_match_lines(expected: &str, actual: &str) -> Result<(), String> {
+    let expected = expand_tokens(expected);
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+
+    if expected_lines.len() != actual_lines.len()
+        || expected_lines
+            .iter()
+            .zip(actual_lines.iter())
+            .any(|(expected, actual)| !lines_match(expected, actual))
+    {
+        return Err(diff_message(&expected_lines, &actual_lines));
+    }
+
+    Ok(())
+}
+
+/// Asserts that `$actual` matches the `$expected` string, line by line, supporting `[..]`
+/// wildcards and the `[EXE]`/`[ROOT]`/`[CWD]` substitution tokens.
+#[macro_export]
+macro_rules! assert_match_str {
+    ($actual:expr, $expected:expr $(,)?) => {{
+        let actual: &str = $actual.as_ref();
+        let expected: &str = $expected.as_ref();
+
+        if let Err(diff) = $crate::assert_match::assert_match_lines(expected, actual) {
+            panic!("{}", diff);
+        }
+    }};
+}
+
+/// Asserts that `$actual` matches the golden file at `$path` (relative to
+/// `CARGO_MANIFEST_DIR`), line by line, supporting `[..]` wildcards and the
+/// `[EXE]`/`[ROOT]`/`[CWD]` substitution tokens.
+///
+/// Set `UPDATE_SNAPSHOTS=1` to (re)write the golden file with `$actual` instead of asserting.
+#[macro_export]
+macro_rules! assert_match_file {
+    ($actual:expr, $path:expr $(,)?) => {{
+        let actual: &str = $actual.as_ref();
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join($path);
+
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, actual)
+                .unwrap_or_else(|error| panic!("failed to update snapshot {:?}. Error = {}", path, error));
+        } else {
+            let expected = std::fs::read_to_string(&path)
+                .unwrap_or_else(|error| panic!("failed to read snapshot {:?}. Error = {}", path, error));
+
+            if let Err(diff) = $crate::assert_match::assert_match_lines(&expected, actual) {
+                panic!("{}", diff);
+            }
+        }
+    }};
+}
+
+pub use assert_match_file;
+pub use assert_match_str;
@@ -9,6 +9,12 @@
 //!   - `before`/`after`: setup and teardown functions for your tests.
 //!   - `env`: set environment variables in the test process.
 //!   - `files`: copy files from your crate directory to the test temporary directory.
+//!   - `isolate_home`: redirect `$HOME` and the XDG base directories to a tempdir so dotfiles never leak.
+//!   - `dotenv`: load environment variables from a `.env` file into the test process.
+//!   - `git_repo`: scaffold a reproducible Git repository fixture in the test tempdir.
+//!   - `fetch`: clone a pinned revision of a remote fixture into the test tempdir, cached locally.
+//! - `assert_match_str!`/`assert_match_file!`: golden-file assertions with `[..]` wildcards.
+//! - a [`run`](crate::prelude::run) function exposing the same isolation as a plain closure, for table-driven or custom test harnesses.
 //!
 //! **Caution:** using `#[sealed_test]` instead of `#[test]` will create a temporary file
 //! and set it to be the test current directory but, nothing stops you from changing that directory
@@ -114,6 +120,110 @@
 //! # }
 //! ```
 //!
+//! ### The `isolate_home` attribute
+//!
+//! Tests that shell out to tools reading `~/.gitconfig`, `$XDG_CONFIG_HOME`, or similar
+//! dotfiles can still leak into the real user home even though the test itself runs in
+//! its own tempdir. `isolate_home` points `HOME`, `XDG_CONFIG_HOME`, `XDG_DATA_HOME` and
+//! `XDG_CACHE_HOME` (and on Windows `USERPROFILE`/`APPDATA`) at a `home/` directory inside
+//! the test tempdir, so the test sees a hermetic home no matter what it reads.
+//!
+//! ```rust
+//! # fn main() {
+//!
+//! use sealed_test::prelude::*;
+//!
+//! #[sealed_test(isolate_home = true)]
+//! fn should_isolate_home() {
+//!     let home = std::env::var("HOME").expect("Failed to get $HOME");
+//!     assert!(home.ends_with("home"));
+//! }
+//! # }
+//! ```
+//!
+//! ### The `dotenv` attribute
+//!
+//! `dotenv` loads `KEY=VALUE` pairs from one or more `.env` files, relative to
+//! `CARGO_MANIFEST_DIR`, into the test process before it runs. Comments (`#`), blank lines,
+//! an optional `export ` prefix and single/double-quoted values are all supported. This lets
+//! a team share a fixture environment across many sealed tests instead of repeating an `env`
+//! list everywhere. When a key appears in both `dotenv` and `env`, the explicit `env` entry wins.
+//!
+//! ```rust
+//! # fn main() {
+//!
+//! use sealed_test::prelude::*;
+//!
+//! #[sealed_test(dotenv = "tests/fixtures/test.env")]
+//! fn should_load_dotenv() {
+//!     let foo = std::env::var("FOO").expect("Failed to get $FOO");
+//!     assert_eq!(foo, "foo");
+//! }
+//! # }
+//! ```
+//!
+//! ### Snapshot assertions
+//!
+//! `assert_match_str!` and `assert_match_file!` compare a string (or the contents of a
+//! golden file, relative to `CARGO_MANIFEST_DIR`) against the actual output line by line.
+//! Within an expected line, `[..]` matches any run of characters, and the tokens `[EXE]`,
+//! `[ROOT]` and `[CWD]` expand to `std::env::consts::EXE_SUFFIX`, the test's tempdir, and
+//! the current directory, so machine-specific paths normalize away. Set `UPDATE_SNAPSHOTS=1`
+//! to rewrite the golden file instead of asserting.
+//!
+//! ```rust
+//! # fn main() {
+//!
+//! use sealed_test::prelude::*;
+//!
+//! #[sealed_test]
+//! fn should_match_snapshot() {
+//!     assert_match_str!("Hello, world!", "Hello, [..]!");
+//! }
+//! # }
+//! ```
+//!
+//! ### The `git_repo` attribute
+//!
+//! Most VCS-related tests start with the same boilerplate: `git init`, then a handful of
+//! `git commit -m ... --allow-empty`. `git_repo` generates that setup for you, with a
+//! deterministic author/committer identity and commit timestamps so commit hashes stay
+//! stable across runs and machines, mirroring how cargo-test-support's `git.rs` builds
+//! repeatable repositories.
+//!
+//! ```rust
+//! # fn main() {
+//!
+//! use sealed_test::prelude::*;
+//!
+//! #[sealed_test(git_repo = { commits = ["c1", "c2"], branch = "main" })]
+//! fn should_scaffold_git_repo() {
+//!     assert!(std::path::PathBuf::from(".git").exists());
+//! }
+//! # }
+//! ```
+//!
+//! ### The `fetch` attribute
+//!
+//! Large binary fixtures don't belong in the crate tree. `fetch` clones a repository at a
+//! pinned revision into a cache directory keyed by the repository and revision, then copies it into the
+//! test tempdir using the same copy machinery as `files`. Since this needs network access,
+//! it is opt-in: set `SEALED_TEST_FETCH=1` to allow it. When it isn't set, the test panics
+//! with a message describing what it would have fetched and how to enable it, so packagers
+//! building from a published `.crate` without network access still get actionable output.
+//!
+//! ```rust, no_run
+//! # fn main() {
+//!
+//! use sealed_test::prelude::*;
+//!
+//! #[sealed_test(fetch = { repo = "https://github.com/example/fixtures", rev = "deadbeef", into = "data" })]
+//! fn should_fetch_fixture() {
+//!     assert!(PathBuf::from("data").exists());
+//! }
+//! # }
+//! ```
+//!
 //! ### Setup and teardown
 //!
 //! Use `before` and `after` to run a rust expression around your tests, typically a function, for instance `setup = setup_function()`.
@@ -137,15 +247,45 @@
 //! }
 //! # }
 //!```
+//!
+//! ### The `run` function
+//!
+//! The attribute macro can only isolate one `#[test]` function at a time. `run` exposes the
+//! same fork/tempdir/env isolation as a plain function, so you can drive it from a
+//! parameterized test, a custom harness, or a single `#[test]` that runs through several
+//! isolated scenarios one after another.
+//!
+//! ```rust, no_run
+//! # fn main() {
+//!
+//! use sealed_test::prelude::*;
+//!
+//! #[test]
+//! fn table_driven_env_test() {
+//!     for (key, value) in [("FOO", "foo"), ("BAR", "bar")] {
+//!         run(
+//!             "table_driven_env_test",
+//!             Config { env: &[(key, value)], ..Default::default() },
+//!             || {
+//!                 assert_eq!(std::env::var(key).unwrap(), value);
+//!             },
+//!         );
+//!     }
+//! }
+//! # }
+//! ```
 #![allow(clippy::test_attr_in_doctest)]
 extern crate sealed_test_derive;
 
+#[doc(hidden)]
+pub mod assert_match;
 pub mod prelude;
+mod run;
 
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
-    use cmd_lib::run_cmd;
+    use cmd_lib::{run_cmd, run_fun};
     use std::env;
     use std::env::VarError;
     use std::path::PathBuf;
@@ -201,6 +341,27 @@ mod tests {
         // Make some assertion in the current test dir
     }
 
+    #[sealed_test(git_repo = { commits = ["c1", "c2"], branch = "main" })]
+    fn should_scaffold_git_repo() {
+        assert!(PathBuf::from(".git").exists());
+
+        let log = run_fun!(git log --format=%s).unwrap();
+        assert!(log.contains("c1"));
+        assert!(log.contains("c2"));
+    }
+
+    #[sealed_test(git_repo = { commits = [{ message = "c1", files = [("foo.txt", "hello")], tag = "v1" }] })]
+    fn should_scaffold_git_repo_with_files_and_tag() {
+        assert!(PathBuf::from("foo.txt").exists());
+
+        let tags = run_fun!(git tag).unwrap();
+        assert!(tags.contains("v1"));
+    }
+
+    #[sealed_test(fetch = { repo = "https://example.com/fixtures.git", rev = "deadbeef", into = "data" })]
+    #[should_panic(expected = "SEALED_TEST_FETCH=1")]
+    fn should_refuse_fetch_without_network_opt_in() {}
+
     #[sealed_test]
     fn a_dummy_test_with_return_type() -> Result<&'static str, &'static str> {
         let current_dir = std::env::current_dir().unwrap();
@@ -284,6 +445,66 @@ mod tests {
         assert_eq!(before, "ok");
     }
 
+    #[sealed_test]
+    fn should_match_str_with_wildcard() {
+        assert_match_str!("Hello, world!", "Hello, [..]!");
+    }
+
+    #[sealed_test]
+    #[should_panic]
+    fn should_panic_on_mismatched_str() {
+        assert_match_str!("Goodbye, world!", "Hello, [..]!");
+    }
+
+    #[sealed_test]
+    fn should_match_golden_file() {
+        assert_match_file!("Hello, world!", "tests/fixtures/hello.snap");
+    }
+
+    #[sealed_test(dotenv = "tests/fixtures/test.env")]
+    fn should_load_dotenv() {
+        let foo = env::var("FOO").expect("Failed to get $FOO");
+        let bar = env::var("BAR").expect("Failed to get $BAR");
+        let qux = env::var("QUX").expect("Failed to get $QUX");
+        let backslash = env::var("BACKSLASH").expect("Failed to get $BACKSLASH");
+
+        assert_eq!(foo, "foo");
+        assert_eq!(bar, "bar baz");
+        assert_eq!(qux, "single quoted");
+        assert_eq!(backslash, "C:\\Users\\new");
+    }
+
+    #[sealed_test(dotenv = "tests/fixtures/test.env", env = [ ("FOO", "overridden") ])]
+    fn explicit_env_should_win_over_dotenv() {
+        let foo = env::var("FOO").expect("Failed to get $FOO");
+        assert_eq!(foo, "overridden");
+    }
+
+    #[sealed_test(isolate_home = true)]
+    fn should_isolate_home() {
+        let home = env::var("HOME").expect("Failed to get $HOME");
+        let xdg_config = env::var("XDG_CONFIG_HOME").expect("Failed to get $XDG_CONFIG_HOME");
+
+        assert!(PathBuf::from(&home).ends_with("home"));
+        assert_eq!(xdg_config, home);
+    }
+
+    #[test]
+    fn should_run_isolated_scenarios_in_sequence() {
+        for (key, value) in [("FOO", "foo"), ("BAR", "bar")] {
+            run(
+                "tests::should_run_isolated_scenarios_in_sequence",
+                Config {
+                    env: &[(key, value)],
+                    ..Default::default()
+                },
+                || {
+                    assert_eq!(env::var(key).unwrap(), value);
+                },
+            );
+        }
+    }
+
     fn setup() {
         std::env::set_var("BEFORE", "ok");
     }
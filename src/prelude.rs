@@ -0,0 +1,5 @@
+//! Everything you need to write sealed tests, in one `use`.
+
+pub use crate::assert_match::{assert_match_file, assert_match_str};
+pub use crate::run::{run, Config};
+pub use sealed_test_derive::sealed_test;
@@ -0,0 +1,110 @@
+//! A runtime counterpart to the `#[sealed_test]` attribute.
+//!
+//! The attribute macro rewrites a `#[test]` function at compile time, which means its
+//! isolation logic is only reachable through a one-attribute-per-function shape. [`run`]
+//! exposes the same fork + tempdir + env isolation as a plain function, so it can be driven
+//! from parameterized tests, custom harnesses, or a single `#[test]` that exercises several
+//! isolated scenarios in sequence.
+
+use std::path::PathBuf;
+
+/// Isolation settings for [`run`], mirroring the `files`/`env`/`isolate_home` attributes.
+#[derive(Default)]
+pub struct Config<'a> {
+    /// Files or directories, relative to `CARGO_MANIFEST_DIR`, copied into the test tempdir.
+    pub files: &'a [&'a str],
+    /// Environment variables set in the test process before the closure runs.
+    pub env: &'a [(&'a str, &'a str)],
+    /// When `true`, redirects `$HOME` and the XDG base directories into the test tempdir.
+    pub isolate_home: bool,
+}
+
+/// Runs `test` in its own forked, sandboxed process: a fresh tempdir is created and becomes
+/// the current directory, `files` and `env` are applied, and `isolate_home` redirects the
+/// home directory, exactly like `#[sealed_test]` does for an attributed function.
+///
+/// `test_name` must be the full path of the calling test as the test harness names it,
+/// without the crate name (for instance `tests::my_test`): the forked process re-runs the
+/// test binary filtered to exactly that name, and with any other name no test, and so not
+/// `test`, runs in it.
+///
+/// ```rust, no_run
+/// # fn main() {
+/// use sealed_test::prelude::*;
+///
+/// run("should_set_env", Config { env: &[("FOO", "bar")], ..Default::default() }, || {
+///     let foo = std::env::var("FOO").unwrap();
+///     assert_eq!(foo, "bar");
+/// });
+/// # }
+/// ```
+pub fn run<T>(test_name: &str, config: Config, test: T)
+where
+    T: FnOnce() + std::panic::RefUnwindSafe,
+{
+    let crate_dir =
+        std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR must be set, run this from a cargo test");
+
+    let files: Vec<String> = config.files.iter().map(|file| file.to_string()).collect();
+    let env: Vec<(String, String)> = config
+        .env
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let isolate_home = config.isolate_home;
+
+    rusty_fork::fork(
+        test_name,
+        rusty_fork::rusty_fork_id!(),
+        |_cmd| {},
+        |child, _file| {
+            let status = child.wait().expect("failed to wait for forked test process");
+            assert!(status.success(), "sealed test `{test_name}` failed");
+        },
+        move || {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            std::env::set_current_dir(&temp_dir).unwrap();
+            std::env::set_var("SEALED_TEST_ROOT_DIR", temp_dir.path());
+
+            if isolate_home {
+                let home_dir = temp_dir.path().join("home");
+                std::fs::create_dir_all(&home_dir).unwrap();
+                std::env::set_var("HOME", &home_dir);
+                std::env::set_var("XDG_CONFIG_HOME", &home_dir);
+                std::env::set_var("XDG_DATA_HOME", &home_dir);
+                std::env::set_var("XDG_CACHE_HOME", &home_dir);
+
+                #[cfg(windows)]
+                {
+                    std::env::set_var("USERPROFILE", &home_dir);
+                    std::env::set_var("APPDATA", &home_dir);
+                }
+            }
+
+            for file in &files {
+                let target = PathBuf::from(file);
+                let target = target.file_name().unwrap().to_str().unwrap();
+                let src = PathBuf::from(&crate_dir).join(file);
+                let dest = std::env::current_dir().unwrap().join(target);
+
+                if src.is_dir() {
+                    let mut opt = fs_extra::dir::CopyOptions::new();
+                    opt.copy_inside = true;
+
+                    if let Err(error) = fs_extra::dir::copy(&src, &dest, &opt) {
+                        panic!("failed to copy {:?} to test directory {:?}. Error = {}", src, dest, error);
+                    }
+                } else if let Err(error) = std::fs::copy(&src, &dest) {
+                    panic!("failed to copy {:?} to test directory {:?}. Error = {}", src, dest, error);
+                }
+            }
+
+            for (key, value) in &env {
+                std::env::set_var(key, value);
+            }
+
+            test();
+        },
+    )
+    .expect("failed to fork sealed test process");
+}